@@ -0,0 +1,94 @@
+use core::fmt;
+use lazy_static::lazy_static; // runtime에 초기화되는 static 변수
+use spin::Mutex;              // os 없이 사용가능한 spinlock mutex
+use x86_64::instructions::port::Port; // UART I/O 포트 접근
+
+const SERIAL_PORT_BASE: u16 = 0x3F8; // COM1
+
+// QEMU `-serial stdio` 등으로 headless 출력을 내보내는 16550 UART 드라이버
+pub struct SerialPort {
+    data: Port<u8>,
+    interrupt_enable: Port<u8>,
+    line_control: Port<u8>,
+    line_status: Port<u8>,
+}
+
+impl SerialPort {
+    // base 포트를 기준으로 UART를 초기화 (38400 baud, 8N1, FIFO 활성화)
+    unsafe fn new(base: u16) -> SerialPort {
+        let mut port = SerialPort {
+            data: Port::new(base),
+            interrupt_enable: Port::new(base + 1),
+            line_control: Port::new(base + 3),
+            line_status: Port::new(base + 5),
+        };
+        port.init(base);
+        port
+    }
+
+    // base를 기준으로 나머지 UART 레지스터 포트를 유도해서 초기화
+    unsafe fn init(&mut self, base: u16) {
+        let mut fifo_control: Port<u8> = Port::new(base + 2);
+        let mut modem_control: Port<u8> = Port::new(base + 4);
+
+        self.interrupt_enable.write(0x00); // 인터럽트 비활성화
+        self.line_control.write(0x80);     // DLAB 설정, divisor 설정 모드로 진입
+        self.data.write(0x03);             // divisor low = 3 -> 38400 baud
+        self.interrupt_enable.write(0x00); // divisor high = 0 (DLAB 상태에서는 interrupt_enable 포트가 divisor high)
+        self.line_control.write(0x03);     // 8비트, 패리티 없음, stop bit 1개(8N1), DLAB 해제
+        fifo_control.write(0xC7);          // FIFO 활성화, 클리어, 14바이트 임계값
+        modem_control.write(0x0B);         // IRQ 활성화(OUT2), RTS/DTR 설정
+        self.interrupt_enable.write(0x01); // 수신 데이터 인터럽트 활성화
+    }
+
+    fn line_status(&mut self) -> u8 {
+        unsafe { self.line_status.read() }
+    }
+
+    // 송신 버퍼가 빌 때까지 기다렸다가 한 바이트를 내보냄
+    pub fn send(&mut self, byte: u8) {
+        while self.line_status() & 0x20 == 0 {}
+        unsafe {
+            self.data.write(byte);
+        }
+    }
+}
+
+impl fmt::Write for SerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            match byte {
+                b'\n' => {
+                    self.send(b'\r');
+                    self.send(b'\n');
+                }
+                byte => self.send(byte),
+            }
+        }
+        Ok(())
+    }
+}
+
+lazy_static! {
+    pub static ref SERIAL1: Mutex<SerialPort> =
+        Mutex::new(unsafe { SerialPort::new(SERIAL_PORT_BASE) });
+}
+
+// serial_print! 매크로
+#[macro_export]
+macro_rules! serial_print {
+    ($($arg:tt)*) => ($crate::serial::_print(format_args!($($arg)*)));
+}
+
+// serial_println! 매크로
+#[macro_export]
+macro_rules! serial_println {
+    () => ($crate::serial_print!("\n"));
+    ($($arg:tt)*) => ($crate::serial_print!("{}\n", format_args!($($arg)*)));
+}
+
+#[doc(hidden)] // 문서에서 숨김(공개 API가 아님)
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    SERIAL1.lock().write_fmt(args).unwrap(); // mutex 잠금 후 출력
+}
@@ -3,6 +3,7 @@ use volatile::Volatile;         // 컴파일러 최적화 방지
 use core::fmt;                  // formatting
 use lazy_static::lazy_static;   // runtime에 초기화되는 static 변수
 use spin::Mutex;                // os 없이 사용가능한 spinlock mutex
+use x86_64::instructions::port::Port; // VGA CRTC 레지스터 접근용 I/O 포트
 
 // global writer instance
 lazy_static! {
@@ -10,6 +11,16 @@ lazy_static! {
         column_position: 0,
         color_code: ColorCode::new(Color::Yellow, Color::Black),
         buffer: unsafe { &mut *(0xb8000 as *mut Buffer) },
+        parse_state: ParseState::Normal,
+        csi_params: [0; CSI_MAX_PARAMS],
+        csi_param_count: 0,
+        csi_current: 0,
+        csi_has_current: false,
+        history: [[ScreenChar { ascii_character: b' ', color_code: ColorCode::new(Color::Yellow, Color::Black) }; BUFFER_WIDTH]; HISTORY_ROWS],
+        history_write: 0,
+        history_len: 0,
+        view_offset: 0,
+        live_snapshot: [[ScreenChar { ascii_character: b' ', color_code: ColorCode::new(Color::Yellow, Color::Black) }; BUFFER_WIDTH]; BUFFER_HEIGHT],
     });
 }
 
@@ -38,12 +49,24 @@ pub enum Color {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
-struct ColorCode(u8);
+pub struct ColorCode(u8);
 
 impl ColorCode {
-    fn new(foreground: Color, background: Color) -> ColorCode {
+    pub fn new(foreground: Color, background: Color) -> ColorCode {
         ColorCode((background as u8) << 4 | (foreground as u8))
     }
+
+    // 속성 바이트의 7번 비트(블링크)를 설정
+    // 블링크 여부와 관계없이 배경색은 하위 3비트로 잘림 (DarkGray..=White는 대응하는 어두운 색이 됨)
+    // BIOS가 상위 배경 비트를 밝은 배경 모드로 재매핑한 경우 블링크는 동작하지 않음
+    pub fn with_blink(foreground: Color, background: Color, blink: bool) -> ColorCode {
+        let background = (background as u8) & 0b0111;
+        let mut code = (background << 4) | (foreground as u8);
+        if blink {
+            code |= 0b1000_0000;
+        }
+        ColorCode(code)
+    }
 }
 
 // vga buffer 의 단일 문자 셀
@@ -57,22 +80,118 @@ struct ScreenChar {
 const BUFFER_HEIGHT: usize = 25;
 const BUFFER_WIDTH: usize = 80;
 
+// 스크롤백으로 보관할 과거 행(row)의 개수
+const HISTORY_ROWS: usize = 200;
+
 // vga text buffer
 #[repr(transparent)]
 struct Buffer {
     chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
 }
 
+// ESC 시퀀스 파서의 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseState {
+    Normal,  // 일반 문자 출력
+    Escape,  // `ESC` 바로 다음
+    Csi,     // `ESC [` 다음, 파라미터 수집 중
+}
+
+// 한 번에 수집 가능한 CSI 파라미터 개수 (고정 용량)
+const CSI_MAX_PARAMS: usize = 8;
+
 // vga buffer에 문자를 쓰는 Writer
 pub struct Writer {
     column_position: usize,         // current cusor 의 열위치
     color_code: ColorCode,          // 현재 사용중인 색상
     buffer: &'static mut Buffer,    // VGA buffer에 대한 참조
+    parse_state: ParseState,            // ESC/CSI 시퀀스 파서 상태
+    csi_params: [u16; CSI_MAX_PARAMS],  // 수집된 CSI 파라미터
+    csi_param_count: usize,             // 수집된 파라미터 개수
+    csi_current: u16,                   // 현재 누적 중인 파라미터 값
+    csi_has_current: bool,              // 현재 파라미터에 숫자가 입력됐는지 여부
+    history: [[ScreenChar; BUFFER_WIDTH]; HISTORY_ROWS], // 스크롤로 밀려난 과거 행들의 원형 버퍼
+    history_write: usize,               // 다음에 기록할 history 슬롯
+    history_len: usize,                 // 채워진 history 행 개수 (HISTORY_ROWS까지)
+    view_offset: usize,                 // 0이면 live 화면, >0이면 그만큼 과거를 보고 있음
+    live_snapshot: [[ScreenChar; BUFFER_WIDTH]; BUFFER_HEIGHT], // 스크롤 시작 시점의 live 화면 보관
 }
 
 impl Writer {
+    // 현재 출력 색상을 변경
+    pub fn set_color(&mut self, fg: Color, bg: Color) {
+        self.color_code = ColorCode::new(fg, bg);
+    }
+
+    // 현재 출력 색상을 조회
+    pub fn color(&self) -> ColorCode {
+        self.color_code
+    }
+
+    // ColorCode 값으로 직접 색상을 복원 (cprintln! 에서 사용)
+    #[doc(hidden)]
+    pub fn restore_color(&mut self, color_code: ColorCode) {
+        self.color_code = color_code;
+    }
+
+    // 현재 색상의 블링크 비트만 켜거나 끔
+    pub fn set_blink(&mut self, blink: bool) {
+        let ColorCode(code) = self.color_code;
+        self.color_code = ColorCode(if blink {
+            code | 0b1000_0000
+        } else {
+            code & !0b1000_0000
+        });
+    }
+
     // 단일 바이트를 화면에 출력
+    // ESC 시퀀스(ANSI/SGR) 도중이면 화면에는 쓰지 않고 상태만 갱신함
     pub fn write_byte(&mut self, byte: u8) {
+        // 스크롤을 보던 중 새 출력이 들어오면 live 화면으로 되돌아감
+        if self.view_offset != 0 {
+            self.view_offset = 0;
+            self.restore_live();
+        }
+
+        match self.parse_state {
+            ParseState::Normal => {
+                if byte == 0x1b {
+                    self.parse_state = ParseState::Escape;
+                    return;
+                }
+            }
+            ParseState::Escape => {
+                // `ESC [` 만 CSI 시퀀스로 인식, 그 외는 조용히 포기
+                if byte == b'[' {
+                    self.parse_state = ParseState::Csi;
+                    self.csi_param_count = 0;
+                    self.csi_current = 0;
+                    self.csi_has_current = false;
+                } else {
+                    self.parse_state = ParseState::Normal;
+                }
+                return;
+            }
+            ParseState::Csi => {
+                match byte {
+                    b'0'..=b'9' => {
+                        self.csi_current = self.csi_current.saturating_mul(10)
+                            + (byte - b'0') as u16;
+                        self.csi_has_current = true;
+                    }
+                    b';' => self.push_csi_param(),
+                    b'm' => {
+                        self.push_csi_param();
+                        self.apply_sgr();
+                        self.parse_state = ParseState::Normal;
+                    }
+                    // 알 수 없는 최종 바이트는 그냥 소비하고 무시
+                    _ => self.parse_state = ParseState::Normal,
+                }
+                return;
+            }
+        }
+
         match byte {
             b'\n' => self.new_line(), // 개행 문자면 새줄로
             byte => {
@@ -93,16 +212,156 @@ impl Writer {
                 self.column_position += 1;
             }
         }
+        self.sync_cursor();
+    }
+
+    // 누적된 CSI 파라미터 하나를 고정 크기 버퍼에 밀어넣음 (생략된 값은 0)
+    fn push_csi_param(&mut self) {
+        if self.csi_param_count < CSI_MAX_PARAMS {
+            self.csi_params[self.csi_param_count] =
+                if self.csi_has_current { self.csi_current } else { 0 };
+            self.csi_param_count += 1;
+        }
+        self.csi_current = 0;
+        self.csi_has_current = false;
+    }
+
+    // 수집된 SGR 코드들을 현재 색상에 적용
+    fn apply_sgr(&mut self) {
+        // 블링크 비트는 SGR이 건드리지 않는 값이므로 그대로 보존
+        let blink_bit = self.color_code.0 & 0b1000_0000;
+        // 4비트 전체로 복원해서, fg만 바꾸는 시퀀스가 밝은/블링크 배경을 깎아먹지 않게 함
+        let mut fg = color_from_u8(self.color_code.0 & 0x0F);
+        let mut bg = color_from_u8((self.color_code.0 >> 4) & 0x0F);
+        // bold(1)는 파라미터 순서와 무관하게 "마지막에" fg를 밝게 만들어야 함
+        // (예: "\x1b[1;31m"과 "\x1b[31;1m"이 모두 LightRed가 되도록)
+        let mut bold = false;
+
+        for &param in &self.csi_params[..self.csi_param_count] {
+            match param {
+                0 => {
+                    fg = Color::Yellow;
+                    bg = Color::Black;
+                    bold = false;
+                }
+                1 => bold = true,
+                n @ 30..=37 => fg = sgr_base_color(n - 30),
+                n @ 40..=47 => bg = sgr_base_color(n - 40),
+                n @ 90..=97 => fg = bright_variant(sgr_base_color(n - 90)),
+                n @ 100..=107 => bg = bright_variant(sgr_base_color(n - 100)),
+                _ => {} // 지원하지 않는 SGR 코드는 무시
+            }
+        }
+
+        if bold {
+            fg = bright_variant(fg);
+        }
+
+        self.color_code = ColorCode(blink_bit | ((bg as u8) << 4) | (fg as u8));
+    }
+
+    // 한 글자를 지우고 (공백으로 되돌리고) 커서를 되돌림
+    pub fn backspace(&mut self) {
+        if self.column_position > 0 {
+            self.column_position -= 1;
+        }
+
+        let row = BUFFER_HEIGHT - 1;
+        let col = self.column_position;
+        self.buffer.chars[row][col].write(ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code,
+        });
+        self.sync_cursor();
+    }
+
+    // 소프트웨어 커서 위치를 실제 VGA 하드웨어 커서에 반영
+    fn sync_cursor(&mut self) {
+        let pos = (BUFFER_HEIGHT - 1) * BUFFER_WIDTH + self.column_position;
+
+        let mut index_port: Port<u8> = Port::new(0x3D4);
+        let mut data_port: Port<u8> = Port::new(0x3D5);
+        unsafe {
+            index_port.write(0x0F);
+            data_port.write((pos & 0xFF) as u8);
+            index_port.write(0x0E);
+            data_port.write(((pos >> 8) & 0xFF) as u8);
+        }
     }
 
-    // 문자열을 화면에 출력
+    // history 원형 버퍼에 한 행을 밀어넣음 (가득 차면 가장 오래된 행을 덮어씀)
+    fn push_history_row(&mut self, row: [ScreenChar; BUFFER_WIDTH]) {
+        self.history[self.history_write] = row;
+        self.history_write = (self.history_write + 1) % HISTORY_ROWS;
+        if self.history_len < HISTORY_ROWS {
+            self.history_len += 1;
+        }
+    }
+
+    // age=0 이면 가장 최근에 밀려난 행, age가 커질수록 더 오래된 행
+    fn history_row_by_age(&self, age: usize) -> [ScreenChar; BUFFER_WIDTH] {
+        let idx = (self.history_write + HISTORY_ROWS - 1 - age) % HISTORY_ROWS;
+        self.history[idx]
+    }
+
+    // 스크롤을 시작하는 시점의 live 화면을 보관
+    fn capture_live_snapshot(&mut self) {
+        for row in 0..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                self.live_snapshot[row][col] = self.buffer.chars[row][col].read();
+            }
+        }
+    }
+
+    // 보관해둔 live 화면을 그대로 복원
+    fn restore_live(&mut self) {
+        for row in 0..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                self.buffer.chars[row][col].write(self.live_snapshot[row][col]);
+            }
+        }
+    }
+
+    // view_offset에 맞춰 history + live_snapshot으로 25행짜리 화면을 다시 그림
+    fn render_view(&mut self) {
+        let offset = self.view_offset;
+        for screen_row in 0..BUFFER_HEIGHT {
+            let row = if screen_row < offset {
+                self.history_row_by_age(offset - 1 - screen_row)
+            } else {
+                self.live_snapshot[screen_row - offset]
+            };
+            for col in 0..BUFFER_WIDTH {
+                self.buffer.chars[screen_row][col].write(row[col]);
+            }
+        }
+    }
+
+    // 위로 스크롤 (더 오래된 내용을 봄)
+    pub fn scroll_up(&mut self, lines: usize) {
+        if self.view_offset == 0 {
+            self.capture_live_snapshot();
+        }
+        self.view_offset = (self.view_offset + lines).min(self.history_len);
+        self.render_view();
+    }
+
+    // 아래로 스크롤 (live 화면 쪽으로 돌아옴)
+    pub fn scroll_down(&mut self, lines: usize) {
+        if self.view_offset == 0 {
+            return;
+        }
+        self.view_offset = self.view_offset.saturating_sub(lines);
+        self.render_view();
+    }
+
+    // 문자열을 화면에 출력 (Code Page 437로 변환해서 출력)
     pub fn write_string(&mut self, s: &str) {
-        for byte in s.bytes() {
-            match byte {
-                // 출력가능한 ASCII 범위와 개행
-                0x20..=0x7e | b'\n' => self.write_byte(byte),
-                // 범위 밖의 문자 처리
-                _ => self.write_byte(0xfe),
+        for c in s.chars() {
+            match c {
+                '\n' => self.write_byte(b'\n'),
+                '\x1b' => self.write_byte(0x1b), // ESC, CSI 파서로 그대로 전달
+                c => self.write_byte(cp437_from_char(c)),
             }
         }
     }
@@ -110,6 +369,13 @@ impl Writer {
     // 새줄로 이동
     // 모든 행을 한줄씩 위로 복사하고 마지막행을 지움
     fn new_line(&mut self) {
+        // 맨 윗 행은 화면에서 밀려나기 전에 history로 보관
+        let mut evicted = [ScreenChar { ascii_character: b' ', color_code: self.color_code }; BUFFER_WIDTH];
+        for col in 0..BUFFER_WIDTH {
+            evicted[col] = self.buffer.chars[0][col].read();
+        }
+        self.push_history_row(evicted);
+
         // 모든 행을 한줄씩 위로 복사
         for row in 1..BUFFER_HEIGHT {
             for col in 0..BUFFER_WIDTH {
@@ -120,6 +386,7 @@ impl Writer {
         // 마지막 행 지우기
         self.clear_row(BUFFER_HEIGHT - 1);
         self.column_position = 0;
+        self.sync_cursor();
     }
 
     // 특정 행을 공백으로 채움
@@ -134,6 +401,150 @@ impl Writer {
     }
 }
 
+// 유니코드 코드포인트를 VGA가 실제로 그리는 Code Page 437 바이트로 변환
+// 매핑에 없는 문자는 0xfe(■, 채워진 사각형)로 대체
+fn cp437_from_char(c: char) -> u8 {
+    match c {
+        // 출력 가능한 ASCII는 CP437에서도 같은 코드를 사용
+        ' '..='~' => c as u8,
+
+        // 라틴-1 보충 영역의 흔한 악센트 문자
+        'Ç' => 0x80,
+        'ü' => 0x81,
+        'é' => 0x82,
+        'â' => 0x83,
+        'ä' => 0x84,
+        'à' => 0x85,
+        'å' => 0x86,
+        'ç' => 0x87,
+        'ê' => 0x88,
+        'ë' => 0x89,
+        'è' => 0x8a,
+        'ï' => 0x8b,
+        'î' => 0x8c,
+        'ì' => 0x8d,
+        'Ä' => 0x8e,
+        'Å' => 0x8f,
+        'É' => 0x90,
+        'ô' => 0x93,
+        'ö' => 0x94,
+        'ò' => 0x95,
+        'û' => 0x96,
+        'ù' => 0x97,
+        'ÿ' => 0x98,
+        'Ö' => 0x99,
+        'Ü' => 0x9a,
+        '¢' => 0x9b,
+        '£' => 0x9c,
+        '¥' => 0x9d,
+
+        // 박스 그리기 및 블록 문자
+        '░' => 0xb0,
+        '▒' => 0xb1,
+        '▓' => 0xb2,
+        '│' => 0xb3,
+        '┤' => 0xb4,
+        '╣' => 0xb9,
+        '║' => 0xba,
+        '╗' => 0xbb,
+        '╝' => 0xbc,
+        '╚' => 0xc8,
+        '╔' => 0xc9,
+        '╩' => 0xca,
+        '╦' => 0xcb,
+        '╠' => 0xcc,
+        '═' => 0xcd,
+        '╬' => 0xce,
+        '─' => 0xc4,
+        '┴' => 0xc1,
+        '┬' => 0xc2,
+        '├' => 0xc3,
+        '┼' => 0xc5,
+        '█' => 0xdb,
+        '▄' => 0xdc,
+        '▌' => 0xdd,
+        '▐' => 0xde,
+        '▀' => 0xdf,
+
+        // 그리스 문자 및 수학 기호
+        'α' => 0xe0,
+        'ß' => 0xe1,
+        'Γ' => 0xe2,
+        'π' => 0xe3,
+        'Σ' => 0xe4,
+        'σ' => 0xe5,
+        'µ' => 0xe6,
+        'τ' => 0xe7,
+        'Φ' => 0xe8,
+        'Θ' => 0xe9,
+        'Ω' => 0xea,
+        'δ' => 0xeb,
+        '∞' => 0xec,
+        'φ' => 0xed,
+        'ε' => 0xee,
+        '±' => 0xf1,
+        '÷' => 0xf6,
+        '°' => 0xf8,
+        '·' => 0xfa,
+        '√' => 0xfb,
+        '²' => 0xfd,
+
+        // 매핑되지 않은 문자는 채워진 사각형(■)으로 대체
+        _ => 0xfe,
+    }
+}
+
+// ColorCode에 저장된 4비트 값을 Color로 복원 (Color의 판별값과 1:1 대응)
+fn color_from_u8(value: u8) -> Color {
+    match value & 0x0F {
+        0 => Color::Black,
+        1 => Color::Blue,
+        2 => Color::Green,
+        3 => Color::Cyan,
+        4 => Color::Red,
+        5 => Color::Magenta,
+        6 => Color::Brown,
+        7 => Color::LightGray,
+        8 => Color::DarkGray,
+        9 => Color::LightBlue,
+        10 => Color::LightGreen,
+        11 => Color::LightCyan,
+        12 => Color::LightRed,
+        13 => Color::Pink,
+        14 => Color::Yellow,
+        _ => Color::White,
+    }
+}
+
+// SGR 기본 8색(30-37/40-47) 팔레트 인덱스를 Color로 변환
+fn sgr_base_color(index: u16) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Brown, // SGR의 yellow
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::LightGray,
+    }
+}
+
+// 기본색을 VGA의 밝은(bright) 계열로 승격
+fn bright_variant(color: Color) -> Color {
+    match color {
+        Color::Black => Color::DarkGray,
+        Color::Blue => Color::LightBlue,
+        Color::Green => Color::LightGreen,
+        Color::Cyan => Color::LightCyan,
+        Color::Red => Color::LightRed,
+        Color::Magenta => Color::Pink,
+        Color::Brown => Color::Yellow,
+        Color::LightGray => Color::White,
+        already_bright => already_bright, // 이미 밝은 색이면 그대로 둠
+    }
+}
+
 // core::fmt::Write 트레이트 구현
 // write! 및 writeln! 매크로 사용가능
 impl fmt::Write for Writer {
@@ -161,4 +572,24 @@ macro_rules! println {
 pub fn _print(args: fmt::Arguments) {
     use core::fmt::Write;
     WRITER.lock().write_fmt(args).unwrap(); // mutex 잠금 후 출력
+
+    // "serial_mirror" 피처가 켜져 있으면 동일한 출력을 시리얼 포트로도 내보냄
+    // (QEMU `-serial stdio`로 헤드리스 테스트/CI에서도 같은 로그를 볼 수 있음)
+    #[cfg(feature = "serial_mirror")]
+    crate::serial::_print(args);
+}
+
+// 지정한 색으로 한 줄을 출력하고, 끝나면 원래 색으로 되돌림
+// ($fg와 $bg는 Color 값)
+#[macro_export]
+macro_rules! cprintln {
+    ($fg:expr, $bg:expr, $($arg:tt)*) => {{
+        use core::fmt::Write;
+        let mut writer = $crate::vga_buffer::WRITER.lock();
+        let previous = writer.color();
+        writer.set_color($fg, $bg);
+        writer.write_fmt(format_args!($($arg)*)).unwrap();
+        writer.write_str("\n").unwrap();
+        writer.restore_color(previous);
+    }};
 }